@@ -1,5 +1,5 @@
 use alloc::{
-    collections::{btree_map::Entry, BTreeMap},
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
     vec::Vec,
 };
 use core::fmt::{self, Display, Formatter};
@@ -13,7 +13,11 @@ use rand::{
 };
 use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{bytesrepr, Key, URef, URefAddr};
+use crate::{
+    bytesrepr,
+    contracts::{Group, MAX_GROUPS, MAX_TOTAL_UREFS},
+    Key, URef, URefAddr,
+};
 
 /// The number of bytes in a serialized [`AccessRights`].
 pub const ACCESS_RIGHTS_SERIALIZED_LENGTH: usize = 1;
@@ -136,11 +140,85 @@ impl Distribution<AccessRights> for Standard {
     }
 }
 
+/// Error returned when a [`ContextAccessRights`] does not know a [`URef`], or does not hold all
+/// of the access rights that the [`URef`] requires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessRightsError {
+    /// The `URef`'s address is not known to the context at all.
+    UnknownURef {
+        /// The address of the unknown `URef`.
+        addr: URefAddr,
+    },
+    /// The context knows of the `URef`, but does not hold all of the rights it requires.
+    InsufficientRights {
+        /// The address of the `URef`.
+        addr: URefAddr,
+        /// The rights the context currently holds for this address.
+        held: AccessRights,
+        /// The rights that were required.
+        required: AccessRights,
+        /// The rights from `required` that `held` does not cover.
+        missing: AccessRights,
+    },
+}
+
+impl Display for AccessRightsError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            AccessRightsError::UnknownURef { addr } => {
+                write!(f, "URef at {:?} is not known to this context", addr)
+            }
+            AccessRightsError::InsufficientRights {
+                addr,
+                held,
+                required,
+                missing,
+            } => write!(
+                f,
+                "URef at {:?} requires {} but only {} is held (missing {})",
+                addr, required, held, missing
+            ),
+        }
+    }
+}
+
+/// Error returned when constructing a group-aware [`ContextAccessRights`] would exceed the
+/// URef budgets a contract header is allowed to define.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupAccessRightsError {
+    /// More groups were supplied than a contract header may define.
+    TooManyGroups {
+        /// The number of groups that were supplied.
+        count: usize,
+    },
+    /// The total number of distinct URefs shared across all groups exceeds `MAX_TOTAL_UREFS`.
+    TooManyGroupUrefs {
+        /// The number of distinct URefs that were supplied across all groups.
+        count: usize,
+    },
+}
+
+impl Display for GroupAccessRightsError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            GroupAccessRightsError::TooManyGroups { count } => {
+                write!(f, "{} groups exceeds the maximum of {}", count, MAX_GROUPS)
+            }
+            GroupAccessRightsError::TooManyGroupUrefs { count } => write!(
+                f,
+                "{} distinct group urefs exceeds the maximum of {}",
+                count, MAX_TOTAL_UREFS
+            ),
+        }
+    }
+}
+
 /// Access rights for a given runtime context.
 #[derive(Debug, PartialEq)]
 pub struct ContextAccessRights {
     context_key: Key,
     access_rights: BTreeMap<URefAddr, AccessRights>,
+    groups: BTreeMap<URefAddr, BTreeSet<Group>>,
 }
 
 impl ContextAccessRights {
@@ -150,11 +228,65 @@ impl ContextAccessRights {
         let mut context_access_rights = ContextAccessRights {
             context_key,
             access_rights: BTreeMap::new(),
+            groups: BTreeMap::new(),
         };
         context_access_rights.do_extend(uref_iter);
         context_access_rights
     }
 
+    /// Creates a new instance of access rights that is also aware of the contract header's user
+    /// groups sharing `uref_iter`'s URefs, in addition to the usual union of rights.
+    ///
+    /// Returns an error if `groups` defines more groups than a contract header may have, or if
+    /// the total number of distinct URefs shared across all groups exceeds `MAX_TOTAL_UREFS`,
+    /// mirroring the budgets enforced on the contract header itself.
+    pub fn new_with_groups<T: IntoIterator<Item = URef>>(
+        context_key: Key,
+        uref_iter: T,
+        groups: BTreeMap<Group, BTreeSet<URefAddr>>,
+    ) -> Result<Self, GroupAccessRightsError> {
+        if groups.len() > MAX_GROUPS as usize {
+            return Err(GroupAccessRightsError::TooManyGroups {
+                count: groups.len(),
+            });
+        }
+
+        let mut groups_by_addr = BTreeMap::<URefAddr, BTreeSet<Group>>::new();
+        for (group, addrs) in &groups {
+            for addr in addrs {
+                groups_by_addr
+                    .entry(*addr)
+                    .or_insert_with(BTreeSet::new)
+                    .insert(group.clone());
+            }
+        }
+
+        if groups_by_addr.len() > MAX_TOTAL_UREFS {
+            return Err(GroupAccessRightsError::TooManyGroupUrefs {
+                count: groups_by_addr.len(),
+            });
+        }
+
+        let mut context_access_rights = ContextAccessRights {
+            context_key,
+            access_rights: BTreeMap::new(),
+            groups: groups_by_addr,
+        };
+        context_access_rights.do_extend(uref_iter);
+        Ok(context_access_rights)
+    }
+
+    /// Returns the addresses of the URefs shared with `group`.
+    pub fn urefs_for_group<'a>(
+        &'a self,
+        group: &'a Group,
+    ) -> impl Iterator<Item = URefAddr> + 'a {
+        self.groups
+            .iter()
+            .filter(move |(_, member_groups)| member_groups.contains(group))
+            .map(|(addr, _)| *addr)
+    }
+
     /// Returns the current context key.
     pub fn context_key(&self) -> Key {
         self.context_key
@@ -179,20 +311,172 @@ impl ContextAccessRights {
         }
     }
 
+    /// Checks whether this context holds sufficient rights to access `uref`, returning a
+    /// structured error describing exactly what is missing otherwise.
+    pub fn check_access_rights(&self, uref: &URef) -> Result<(), AccessRightsError> {
+        let required = uref.access_rights();
+        match self.access_rights.get(&uref.addr()) {
+            Some(held) if held.contains(required) => Ok(()),
+            Some(held) => Err(AccessRightsError::InsufficientRights {
+                addr: uref.addr(),
+                held: *held,
+                required,
+                missing: required & !*held,
+            }),
+            None => Err(AccessRightsError::UnknownURef { addr: uref.addr() }),
+        }
+    }
+
     /// Checks whether given uref has enough access rights.
     pub fn has_access_rights_to_uref(&self, uref: &URef) -> bool {
-        if let Some(known_rights) = self.access_rights.get(&uref.addr()) {
-            let rights_to_check = uref.access_rights();
-            known_rights.contains(rights_to_check)
-        } else {
-            // URef is not known
-            false
+        self.check_access_rights(uref).is_ok()
+    }
+
+    /// Derives a context for a callee from this context, attenuating rights at the boundary.
+    ///
+    /// For each `URef` in `forwarded`, the child's rights are the intersection of the rights
+    /// already held here with the rights carried on the forwarded `URef` itself, so a callee
+    /// can never end up with more authority than its caller had. Any forwarded `URef` whose
+    /// address is unknown to this context is omitted from the child entirely, rather than
+    /// being granted on trust. Group membership for a forwarded `URef` is carried over the same
+    /// way: the child only ever knows about the groups of the `URef`s actually forwarded to it.
+    pub fn derive_child(&self, forwarded: &[URef]) -> ContextAccessRights {
+        let mut access_rights = BTreeMap::new();
+        let mut groups = BTreeMap::new();
+        for uref in forwarded {
+            if let Some(held_rights) = self.access_rights.get(&uref.addr()) {
+                let attenuated_rights = held_rights.intersection(uref.access_rights());
+                access_rights.insert(uref.addr(), attenuated_rights);
+                if let Some(member_groups) = self.groups.get(&uref.addr()) {
+                    groups.insert(uref.addr(), member_groups.clone());
+                }
+            }
+        }
+        ContextAccessRights {
+            context_key: self.context_key,
+            access_rights,
+            groups,
+        }
+    }
+
+    /// Returns `uref` clamped to the access rights `requested`, further clamped to what this
+    /// context already holds for it, or `None` if `uref`'s address is not known here.
+    ///
+    /// This is the single-`URef` counterpart of [`Self::derive_child`], useful when a contract
+    /// wants to pass a deliberately weaker capability to a callee.
+    pub fn attenuate(&self, uref: URef, requested: AccessRights) -> Option<URef> {
+        let held_rights = *self.access_rights.get(&uref.addr())?;
+        let attenuated_rights = held_rights.intersection(requested);
+        Some(URef::new(uref.addr(), attenuated_rights))
+    }
+
+    /// Fully revokes whatever rights this context holds for the `URef` at `addr`, as if it had
+    /// never been granted. A subsequent lookup for that address is treated as unknown, and it is
+    /// also dropped from any group it was tracked as belonging to.
+    pub fn remove(&mut self, addr: &URefAddr) {
+        self.access_rights.remove(addr);
+        self.groups.remove(addr);
+    }
+
+    /// Downgrades the stored rights for `uref`'s address by intersecting them with `uref`'s own
+    /// access rights. This can only ever narrow what is held, never widen it. If the resulting
+    /// rights are empty, the entry is dropped entirely rather than being kept as `NONE`, and its
+    /// group membership is dropped along with it.
+    pub fn restrict(&mut self, uref: &URef) {
+        if let Entry::Occupied(mut entry) = self.access_rights.entry(uref.addr()) {
+            let restricted_rights = entry.get().intersection(uref.access_rights());
+            if restricted_rights.is_none() {
+                entry.remove();
+                self.groups.remove(&uref.addr());
+            } else {
+                *entry.get_mut() = restricted_rights;
+            }
         }
     }
 }
 
+/// An ordered stack of per-frame [`ContextAccessRights`], one frame per currently executing
+/// contract or session call.
+///
+/// Pushing a new frame attenuates whatever `URef`s are forwarded into it against the rights
+/// held by the frame currently on top, so authority can only ever shrink as calls nest deeper.
+/// Resolving a `URef`'s rights only ever consults the top frame, which is what a contract
+/// should see of its own authority; the engine can still look up any frame by its `Key` to
+/// answer bookkeeping questions about the call stack as a whole.
+#[derive(Debug, PartialEq)]
+pub struct CallStackAccessRights {
+    frames: Vec<ContextAccessRights>,
+}
+
+impl CallStackAccessRights {
+    /// Creates a new call stack access rights, seeded with a single root frame built from
+    /// `context_key` and `uref_iter`.
+    pub fn new<T: IntoIterator<Item = URef>>(context_key: Key, uref_iter: T) -> Self {
+        let mut frames = Vec::new();
+        frames.push(ContextAccessRights::new(context_key, uref_iter));
+        CallStackAccessRights { frames }
+    }
+
+    /// Creates a new call stack access rights, seeded with a single root frame built from
+    /// `context_key`, `uref_iter` and the contract header's `groups`, via
+    /// [`ContextAccessRights::new_with_groups`].
+    pub fn new_with_groups<T: IntoIterator<Item = URef>>(
+        context_key: Key,
+        uref_iter: T,
+        groups: BTreeMap<Group, BTreeSet<URefAddr>>,
+    ) -> Result<Self, GroupAccessRightsError> {
+        let root_frame = ContextAccessRights::new_with_groups(context_key, uref_iter, groups)?;
+        let mut frames = Vec::new();
+        frames.push(root_frame);
+        Ok(CallStackAccessRights { frames })
+    }
+
+    /// Returns the frame currently on top of the stack, if the stack is non-empty.
+    pub fn current_frame(&self) -> Option<&ContextAccessRights> {
+        self.frames.last()
+    }
+
+    /// Pushes a new frame for `context_key`, attenuating `urefs` against the rights held by the
+    /// frame currently on top of the stack so a callee can never gain more authority than its
+    /// caller had. If the stack has been fully unwound (no frame to attenuate against), `urefs`
+    /// are granted directly to the new frame, exactly as [`Self::new`] grants the initial root
+    /// frame's `URef`s.
+    pub fn push_frame(&mut self, context_key: Key, urefs: &[URef]) {
+        let mut frame = match self.current_frame() {
+            Some(parent_frame) => parent_frame.derive_child(urefs),
+            None => ContextAccessRights::new(context_key, urefs.iter().copied()),
+        };
+        frame.context_key = context_key;
+        self.frames.push(frame);
+    }
+
+    /// Pops the top frame off the stack and returns it, or `None` if the stack is empty.
+    pub fn pop_frame(&mut self) -> Option<ContextAccessRights> {
+        self.frames.pop()
+    }
+
+    /// Checks whether the frame currently on top of the stack holds sufficient rights to
+    /// access `uref`.
+    pub fn check_access_rights(&self, uref: &URef) -> Result<(), AccessRightsError> {
+        match self.current_frame() {
+            Some(frame) => frame.check_access_rights(uref),
+            None => Err(AccessRightsError::UnknownURef { addr: uref.addr() }),
+        }
+    }
+
+    /// Looks up the frame matching `context_key`, searching the entire stack rather than only
+    /// the top.
+    pub fn frame_by_key(&self, context_key: &Key) -> Option<&ContextAccessRights> {
+        self.frames
+            .iter()
+            .find(|frame| frame.context_key() == *context_key)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use alloc::format;
+
     use super::*;
     use crate::UREF_ADDR_LENGTH;
 
@@ -300,4 +584,331 @@ mod tests {
         expected_rights.insert(UREF_ADDRESS, AccessRights::READ_ADD);
         assert_eq!(context_rights.access_rights, expected_rights);
     }
+
+    #[test]
+    fn should_derive_attenuated_child_context() {
+        let context_rights = ContextAccessRights::new(KEY, vec![UREF_READ_ADD]);
+
+        // Forwarding with full rights should still be clamped to what the parent holds.
+        let child_rights = context_rights.derive_child(&[UREF_READ_ADD_WRITE]);
+        assert!(child_rights.has_access_rights_to_uref(&UREF_READ_ADD));
+        assert!(!child_rights.has_access_rights_to_uref(&UREF_WRITE));
+
+        // Forwarding with a strict subset of rights should clamp to that subset.
+        let child_rights = context_rights.derive_child(&[UREF_READ]);
+        assert!(child_rights.has_access_rights_to_uref(&UREF_READ));
+        assert!(!child_rights.has_access_rights_to_uref(&UREF_ADD));
+    }
+
+    #[test]
+    fn should_not_derive_rights_for_unknown_uref_in_child() {
+        let context_rights = ContextAccessRights::new(KEY, vec![UREF_READ_ADD]);
+
+        let unknown_uref = URef::new([2; UREF_ADDR_LENGTH], AccessRights::READ_ADD_WRITE);
+        let child_rights = context_rights.derive_child(&[unknown_uref]);
+        assert!(!child_rights.has_access_rights_to_uref(&unknown_uref));
+        assert!(!child_rights
+            .has_access_rights_to_uref(&URef::new([2; UREF_ADDR_LENGTH], AccessRights::empty())));
+    }
+
+    #[test]
+    fn should_attenuate_single_uref() {
+        let context_rights = ContextAccessRights::new(KEY, vec![UREF_READ_ADD]);
+
+        let attenuated = context_rights
+            .attenuate(UREF_READ_ADD, AccessRights::READ)
+            .expect("should attenuate known uref");
+        assert_eq!(attenuated.access_rights(), AccessRights::READ);
+
+        // Requesting more than is held should still clamp to what is held.
+        let attenuated = context_rights
+            .attenuate(UREF_READ_ADD, AccessRights::READ_ADD_WRITE)
+            .expect("should attenuate known uref");
+        assert_eq!(attenuated.access_rights(), AccessRights::READ_ADD);
+
+        // Unknown urefs cannot be attenuated.
+        let unknown_uref = URef::new([2; UREF_ADDR_LENGTH], AccessRights::READ_ADD_WRITE);
+        assert_eq!(
+            context_rights.attenuate(unknown_uref, AccessRights::READ),
+            None
+        );
+    }
+
+    #[test]
+    fn should_restrict_access_rights() {
+        let mut context_rights = ContextAccessRights::new(KEY, vec![UREF_READ_ADD_WRITE]);
+
+        // Downgrade READ_ADD_WRITE to READ via restrict.
+        context_rights.restrict(&UREF_READ);
+        assert!(context_rights.has_access_rights_to_uref(&UREF_READ));
+        assert!(!context_rights.has_access_rights_to_uref(&UREF_WRITE));
+        assert!(!context_rights.has_access_rights_to_uref(&UREF_ADD));
+
+        // Restricting down to NONE should remove the entry, treating the addr as unknown.
+        context_rights.restrict(&UREF_NO_PERMISSIONS);
+        assert!(!context_rights.has_access_rights_to_uref(&UREF_NO_PERMISSIONS));
+        assert_eq!(context_rights.access_rights, BTreeMap::new());
+    }
+
+    #[test]
+    fn should_remove_access_rights() {
+        let mut context_rights = ContextAccessRights::new(KEY, vec![UREF_READ_ADD_WRITE]);
+
+        context_rights.remove(&UREF_ADDRESS);
+        assert!(!context_rights.has_access_rights_to_uref(&UREF_NO_PERMISSIONS));
+        assert_eq!(context_rights.access_rights, BTreeMap::new());
+    }
+
+    #[test]
+    fn should_drop_group_membership_on_remove() {
+        let group_a = Group::new("group_a");
+        let mut groups = BTreeMap::new();
+        groups.insert(group_a.clone(), BTreeSet::from_iter([UREF_ADDRESS]));
+
+        let mut context_rights =
+            ContextAccessRights::new_with_groups(KEY, vec![UREF_READ_ADD_WRITE], groups)
+                .expect("should construct within group budgets");
+        assert_eq!(
+            context_rights.urefs_for_group(&group_a).collect::<Vec<_>>(),
+            vec![UREF_ADDRESS]
+        );
+
+        context_rights.remove(&UREF_ADDRESS);
+        assert_eq!(
+            context_rights.urefs_for_group(&group_a).collect::<Vec<_>>(),
+            Vec::<URefAddr>::new()
+        );
+    }
+
+    #[test]
+    fn should_drop_group_membership_on_restrict_to_none() {
+        let group_a = Group::new("group_a");
+        let mut groups = BTreeMap::new();
+        groups.insert(group_a.clone(), BTreeSet::from_iter([UREF_ADDRESS]));
+
+        let mut context_rights =
+            ContextAccessRights::new_with_groups(KEY, vec![UREF_READ_ADD_WRITE], groups)
+                .expect("should construct within group budgets");
+        assert_eq!(
+            context_rights.urefs_for_group(&group_a).collect::<Vec<_>>(),
+            vec![UREF_ADDRESS]
+        );
+
+        // Restricting to READ only should keep the group membership, since the addr is still
+        // tracked with non-empty rights.
+        context_rights.restrict(&UREF_READ);
+        assert_eq!(
+            context_rights.urefs_for_group(&group_a).collect::<Vec<_>>(),
+            vec![UREF_ADDRESS]
+        );
+
+        // Restricting all the way down to NONE should remove the entry, dropping its group
+        // membership along with it.
+        context_rights.restrict(&UREF_NO_PERMISSIONS);
+        assert_eq!(
+            context_rights.urefs_for_group(&group_a).collect::<Vec<_>>(),
+            Vec::<URefAddr>::new()
+        );
+    }
+
+    #[test]
+    fn should_report_unknown_uref_error() {
+        let context_rights = ContextAccessRights::new(KEY, vec![UREF_READ_ADD]);
+
+        let unknown_uref = URef::new([2; UREF_ADDR_LENGTH], AccessRights::READ);
+        assert_eq!(
+            context_rights.check_access_rights(&unknown_uref),
+            Err(AccessRightsError::UnknownURef {
+                addr: unknown_uref.addr()
+            })
+        );
+    }
+
+    #[test]
+    fn should_report_insufficient_rights_error() {
+        let context_rights = ContextAccessRights::new(KEY, vec![UREF_READ_ADD]);
+
+        assert_eq!(
+            context_rights.check_access_rights(&UREF_READ_ADD_WRITE),
+            Err(AccessRightsError::InsufficientRights {
+                addr: UREF_ADDRESS,
+                held: AccessRights::READ_ADD,
+                required: AccessRights::READ_ADD_WRITE,
+                missing: AccessRights::WRITE,
+            })
+        );
+        assert_eq!(context_rights.check_access_rights(&UREF_READ_ADD), Ok(()));
+    }
+
+    #[test]
+    fn should_resolve_uref_against_top_frame_only() {
+        let other_key: Key = Key::URef(URef::new([2; UREF_ADDR_LENGTH], AccessRights::empty()));
+        let mut call_stack_rights = CallStackAccessRights::new(KEY, vec![UREF_READ_ADD_WRITE]);
+
+        // A frame forwarding only READ should attenuate, not inherit the full parent rights.
+        call_stack_rights.push_frame(other_key, &[UREF_READ]);
+        assert!(call_stack_rights.check_access_rights(&UREF_READ).is_ok());
+        assert!(call_stack_rights.check_access_rights(&UREF_WRITE).is_err());
+
+        // Popping returns to the parent frame, which still holds the full rights.
+        call_stack_rights.pop_frame();
+        assert!(call_stack_rights
+            .check_access_rights(&UREF_READ_ADD_WRITE)
+            .is_ok());
+    }
+
+    #[test]
+    fn should_not_drop_urefs_when_pushing_after_full_unwind() {
+        let mut call_stack_rights = CallStackAccessRights::new(KEY, vec![UREF_READ_ADD]);
+
+        // Fully unwind the stack.
+        call_stack_rights.pop_frame();
+        assert!(call_stack_rights.current_frame().is_none());
+
+        // Pushing with no parent frame to attenuate against should grant the urefs directly,
+        // not silently drop them.
+        call_stack_rights.push_frame(KEY, &[UREF_READ_ADD]);
+        assert!(call_stack_rights.check_access_rights(&UREF_READ_ADD).is_ok());
+    }
+
+    #[test]
+    fn should_not_attenuate_above_what_pushing_frame_holds() {
+        let other_key: Key = Key::URef(URef::new([2; UREF_ADDR_LENGTH], AccessRights::empty()));
+        let mut call_stack_rights = CallStackAccessRights::new(KEY, vec![UREF_READ]);
+
+        // Forwarding a uref with more rights than the parent holds should still be clamped.
+        call_stack_rights.push_frame(other_key, &[UREF_READ_ADD_WRITE]);
+        assert!(call_stack_rights.check_access_rights(&UREF_READ).is_ok());
+        assert!(call_stack_rights.check_access_rights(&UREF_ADD).is_err());
+    }
+
+    #[test]
+    fn should_look_up_any_frame_by_key() {
+        let other_key: Key = Key::URef(URef::new([2; UREF_ADDR_LENGTH], AccessRights::empty()));
+        let mut call_stack_rights = CallStackAccessRights::new(KEY, vec![UREF_READ_ADD]);
+        call_stack_rights.push_frame(other_key, &[UREF_READ]);
+
+        assert_eq!(
+            call_stack_rights.frame_by_key(&KEY).map(|f| f.context_key()),
+            Some(KEY)
+        );
+        assert_eq!(
+            call_stack_rights
+                .frame_by_key(&other_key)
+                .map(|f| f.context_key()),
+            Some(other_key)
+        );
+    }
+
+    #[test]
+    fn should_resolve_urefs_for_group() {
+        let group_a = Group::new("group_a");
+        let group_b = Group::new("group_b");
+
+        let other_addr = [2; UREF_ADDR_LENGTH];
+        let mut groups = BTreeMap::new();
+        groups.insert(group_a.clone(), BTreeSet::from_iter([UREF_ADDRESS]));
+        groups.insert(
+            group_b.clone(),
+            BTreeSet::from_iter([UREF_ADDRESS, other_addr]),
+        );
+
+        let context_rights =
+            ContextAccessRights::new_with_groups(KEY, vec![UREF_READ_ADD], groups)
+                .expect("should construct within group budgets");
+
+        let mut group_a_urefs: Vec<_> = context_rights.urefs_for_group(&group_a).collect();
+        group_a_urefs.sort();
+        assert_eq!(group_a_urefs, vec![UREF_ADDRESS]);
+
+        let mut group_b_urefs: Vec<_> = context_rights.urefs_for_group(&group_b).collect();
+        group_b_urefs.sort();
+        assert_eq!(group_b_urefs, vec![UREF_ADDRESS, other_addr]);
+    }
+
+    #[test]
+    fn should_carry_group_membership_through_derive_child() {
+        let group_a = Group::new("group_a");
+        let mut groups = BTreeMap::new();
+        groups.insert(group_a.clone(), BTreeSet::from_iter([UREF_ADDRESS]));
+
+        let root_rights = ContextAccessRights::new_with_groups(KEY, vec![UREF_READ_ADD], groups)
+            .expect("should construct within group budgets");
+
+        // A pushed frame should still be able to answer "is this uref shared with group_a?"
+        let child_rights = root_rights.derive_child(&[UREF_READ]);
+        let group_a_urefs: Vec<_> = child_rights.urefs_for_group(&group_a).collect();
+        assert_eq!(group_a_urefs, vec![UREF_ADDRESS]);
+
+        // Urefs that were not forwarded should not leak their group membership either.
+        let unknown_uref = URef::new([2; UREF_ADDR_LENGTH], AccessRights::READ);
+        let child_rights = root_rights.derive_child(&[unknown_uref]);
+        assert_eq!(child_rights.urefs_for_group(&group_a).collect::<Vec<_>>(), vec![]);
+    }
+
+    #[test]
+    fn should_carry_group_membership_through_pushed_frame() {
+        let group_a = Group::new("group_a");
+        let mut groups = BTreeMap::new();
+        groups.insert(group_a.clone(), BTreeSet::from_iter([UREF_ADDRESS]));
+
+        let mut call_stack_rights =
+            CallStackAccessRights::new_with_groups(KEY, vec![UREF_READ_ADD], groups)
+                .expect("should construct within group budgets");
+
+        let other_key: Key = Key::URef(URef::new([2; UREF_ADDR_LENGTH], AccessRights::empty()));
+        call_stack_rights.push_frame(other_key, &[UREF_READ]);
+
+        let pushed_frame = call_stack_rights
+            .current_frame()
+            .expect("frame should have been pushed");
+        assert_eq!(
+            pushed_frame.urefs_for_group(&group_a).collect::<Vec<_>>(),
+            vec![UREF_ADDRESS]
+        );
+    }
+
+    #[test]
+    fn should_reject_too_many_group_urefs() {
+        let mut groups = BTreeMap::new();
+        let addrs: BTreeSet<URefAddr> = (0..=MAX_TOTAL_UREFS as u8)
+            .map(|i| [i; UREF_ADDR_LENGTH])
+            .collect();
+        groups.insert(Group::new("group_a"), addrs);
+
+        assert_eq!(
+            ContextAccessRights::new_with_groups(KEY, vec![UREF_READ_ADD], groups),
+            Err(GroupAccessRightsError::TooManyGroupUrefs {
+                count: MAX_TOTAL_UREFS + 1
+            })
+        );
+    }
+
+    #[test]
+    fn should_reject_too_many_groups() {
+        let groups: BTreeMap<Group, BTreeSet<URefAddr>> = (0..=MAX_GROUPS)
+            .map(|i| (Group::new(format!("group_{}", i)), BTreeSet::new()))
+            .collect();
+
+        assert_eq!(
+            ContextAccessRights::new_with_groups(KEY, vec![UREF_READ_ADD], groups),
+            Err(GroupAccessRightsError::TooManyGroups {
+                count: MAX_GROUPS as usize + 1
+            })
+        );
+    }
+
+    #[test]
+    fn should_reject_too_many_groups_in_call_stack() {
+        let groups: BTreeMap<Group, BTreeSet<URefAddr>> = (0..=MAX_GROUPS)
+            .map(|i| (Group::new(format!("group_{}", i)), BTreeSet::new()))
+            .collect();
+
+        assert_eq!(
+            CallStackAccessRights::new_with_groups(KEY, vec![UREF_READ_ADD], groups),
+            Err(GroupAccessRightsError::TooManyGroups {
+                count: MAX_GROUPS as usize + 1
+            })
+        );
+    }
 }